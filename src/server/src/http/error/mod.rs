@@ -4,6 +4,7 @@ use axum::http::{HeaderMap, HeaderValue, Response, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
 use sqlx::error::DatabaseError;
+use crate::rummy::game::error::GameError;
 use std::borrow::Cow;
 use std::collections::HashMap;
 
@@ -83,6 +84,17 @@ pub enum HttpError {
     /// for security reasons.
     #[error("an internal server error occurred")]
     Anyhow(#[from] anyhow::Error),
+
+    /// Return `409 Conflict` or `422 Unprocessable Entity` on a `GameError`.
+    ///
+    /// Via the generated `From<GameError> for HttpError` impl, game actions can use `?`
+    /// in handler functions. An action in the wrong game phase maps to `409 Conflict`,
+    /// and any other invalid action maps to `422 Unprocessable Entity`.
+    ///
+    /// Unlike the internal errors above, the message is returned to the client,
+    /// since it only describes what was wrong with their action.
+    #[error("{0}")]
+    Game(#[from] GameError),
 }
 
 impl HttpError {
@@ -115,6 +127,8 @@ impl HttpError {
             Self::NotFound => StatusCode::NOT_FOUND,
             Self::UnprocessableEntity { .. } => StatusCode::UNPROCESSABLE_ENTITY,
             Self::Sqlx(_) | Self::Anyhow(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Game(GameError::WrongPhase) => StatusCode::CONFLICT,
+            Self::Game(_) => StatusCode::UNPROCESSABLE_ENTITY,
         }
     }
 }
@@ -219,3 +233,19 @@ where
         })
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_error_status_codes() {
+        assert_eq!(HttpError::from(GameError::WrongPhase).status_code(), StatusCode::CONFLICT);
+        assert_eq!(HttpError::from(GameError::EmptyDiscardPile).status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            HttpError::from(GameError::InvalidIndex { index: 3, len: 2 }).status_code(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use super::{card::Card, suit_rank::{Rank, Suit}};
+
+/// Counts how many cards of each rank are in `cards`.
+pub fn rank_counts(cards: &[Card]) -> HashMap<Rank, usize> {
+    let mut counts = HashMap::new();
+    for card in cards {
+        *counts.entry(card.rank).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Counts how many cards of each suit are in `cards`.
+pub fn suit_counts(cards: &[Card]) -> HashMap<Suit, usize> {
+    let mut counts = HashMap::new();
+    for card in cards {
+        *counts.entry(card.suit).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Returns each rank with at least 2 cards in `cards` (ie at most 1 card away from a set),
+/// in ascending order.
+/// 
+/// **Note**: Wildcards aren't taken into account; they're counted under their own rank.
+pub fn near_sets(cards: &[Card]) -> Vec<Rank> {
+    let mut ranks: Vec<Rank> = rank_counts(cards)
+        .into_iter()
+        .filter(|&(_, count)| count >= 2)
+        .map(|(rank, _)| rank)
+        .collect();
+    ranks.sort();
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use super::*;
+    use crate::rummy::cards::deck::{Deck, DeckConfig};
+
+    /// A sample hand, in no particular order.
+    fn sample_hand() -> Vec<Card> {
+        let deck = Rc::new(Deck::new(DeckConfig::default()).unwrap());
+        [
+            (Rank::King, Suit::Spades), (Rank::Seven, Suit::Diamonds), (Rank::King, Suit::Hearts),
+            (Rank::Five, Suit::Hearts), (Rank::Seven, Suit::Clubs), (Rank::Two, Suit::Spades),
+            (Rank::Seven, Suit::Hearts), (Rank::Five, Suit::Spades)
+        ]
+            .into_iter()
+            .map(|(rank, suit)| Card::new(deck.clone(), rank, suit))
+            .collect()
+    }
+
+    #[test]
+    fn counts_ranks() {
+        let counts = rank_counts(&sample_hand());
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[&Rank::Two], 1);
+        assert_eq!(counts[&Rank::Five], 2);
+        assert_eq!(counts[&Rank::Seven], 3);
+        assert_eq!(counts[&Rank::King], 2);
+    }
+
+    #[test]
+    fn counts_suits() {
+        let counts = suit_counts(&sample_hand());
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[&Suit::Spades], 3);
+        assert_eq!(counts[&Suit::Hearts], 3);
+        assert_eq!(counts[&Suit::Diamonds], 1);
+        assert_eq!(counts[&Suit::Clubs], 1);
+    }
+
+    #[test]
+    fn near_sets_in_ascending_order() {
+        assert_eq!(near_sets(&sample_hand()), vec![Rank::Five, Rank::Seven, Rank::King]);
+        assert!(near_sets(&[]).is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Serialize, Deserialize};
 use super::{deck::Deck, suit_rank::{Rank, Suit}};
-use std::{rc::Rc, cmp::Ordering};
+use std::{rc::Rc, cmp::Ordering, fmt};
 
 /// A card.
 /// 
@@ -33,6 +33,97 @@ impl Card {
     pub fn data(&self) -> (Rank, Suit) {
         (self.rank, self.suit)
     }
+
+    /// Ties the card to `deck`.
+    /// 
+    /// As a card's deck is skipped by serde, this must be called after deserializing it.
+    pub(crate) fn set_deck(&mut self, deck: Rc<Deck>) {
+        self.deck = deck;
+    }
+
+    /// Returns whether the card is a wildcard under its deck's configuration;
+    /// ie it has the deck's `wildcard_rank`, or is a Joker while the deck uses Jokers.
+    pub fn is_wildcard(&self) -> bool {
+        let deck_config = self.deck.get_config();
+        deck_config.wildcard_rank == Some(self.rank)
+            || (deck_config.use_joker && self.rank == Rank::Joker)
+    }
+
+    /// Packs the card's rank and suit into a single byte;
+    /// the rank is in the high 4 bits, and the suit in the low 4 bits.
+    /// 
+    /// A Joker (having both Joker rank and suit) is always `0`.
+    pub fn to_byte(&self) -> u8 {
+        ((self.rank as u8) << 4) | self.suit as u8
+    }
+
+    /// Unpacks a card from a byte created by `to_byte`, tying it to `deck`.
+    /// 
+    /// Returns `Err` if the rank or suit is out of range,
+    /// only one of them is Joker, or it's a Joker while `deck` doesn't use Jokers.
+    pub fn from_byte(byte: u8, deck: Rc<Deck>) -> Result<Self, String> {
+        let rank = Rank::try_from(byte >> 4)?;
+        let suit = Suit::try_from(byte & 0x0F)?;
+        if (rank == Rank::Joker) != (suit == Suit::Joker) {
+            return Err(format!("Invalid card byte ({byte}); Joker rank and suit must be used together"));
+        }
+        if rank == Rank::Joker && !deck.get_config().use_joker {
+            return Err(format!("Invalid card byte ({byte}); the deck doesn't use Jokers"));
+        }
+        Ok(Card::new(deck, rank, suit))
+    }
+
+    /// Parses a card's rank and suit from short notation (case-insensitive),
+    /// being the rank followed by the suit initial (ie `"AS"`, `"10H"`, `"TD"`), or `"Joker"`.
+    /// 
+    /// As a card must be tied to a deck, only the rank and suit are returned.
+    /// 
+    /// Like `from_byte`, returns `Err` if only one of the rank and suit is Joker (ie `"JokerS"`).
+    pub fn parse_data(s: &str) -> Result<(Rank, Suit), String> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("joker") {
+            return Ok((Rank::Joker, Suit::Joker));
+        }
+        if s.len() < 2 || !s.is_ascii() {
+            return Err(format!("Invalid card '{s}' (expected a rank followed by a suit, ie AS or 10H)"));
+        }
+
+        let (rank, suit) = s.split_at(s.len() - 1);
+        let (rank, suit): (Rank, Suit) = (rank.parse()?, suit.parse()?);
+        if (rank == Rank::Joker) != (suit == Suit::Joker) {
+            return Err(format!("Invalid card '{s}'; Joker rank and suit must be used together"));
+        }
+        Ok((rank, suit))
+    }
+}
+
+
+/// Displays the card in short notation (ie `AS`, `10H`, or `Joker`);
+/// this can be parsed back with `Card::parse_data`.
+/// 
+/// A card with only one of a Joker rank or suit (which a deck never creates) is also displayed as `Joker`.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.rank == Rank::Joker {
+            return write!(f, "Joker");
+        }
+
+        let rank = match self.rank {
+            Rank::Ace => "A".to_owned(),
+            Rank::Jack => "J".to_owned(),
+            Rank::Queen => "Q".to_owned(),
+            Rank::King => "K".to_owned(),
+            rank => (rank as u8).to_string()
+        };
+        let suit = match self.suit {
+            Suit::Clubs => "C",
+            Suit::Diamonds => "D",
+            Suit::Hearts => "H",
+            Suit::Spades => "S",
+            Suit::Joker => return write!(f, "Joker")
+        };
+        write!(f, "{rank}{suit}")
+    }
 }
 
 
@@ -80,4 +171,76 @@ impl PartialOrd for Card {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rummy::cards::deck::DeckConfig;
+
+    /// A deck containing every card, including Jokers.
+    fn full_deck() -> Deck {
+        Deck::new(DeckConfig { use_joker: true, ..DeckConfig::default() }).unwrap()
+    }
+
+    #[test]
+    fn parse_valid_cards() {
+        assert_eq!(Card::parse_data("AS"), Ok((Rank::Ace, Suit::Spades)));
+        assert_eq!(Card::parse_data("10h"), Ok((Rank::Ten, Suit::Hearts)));
+        assert_eq!(Card::parse_data("TD"), Ok((Rank::Ten, Suit::Diamonds)));
+        assert_eq!(Card::parse_data(" kc "), Ok((Rank::King, Suit::Clubs)));
+        assert_eq!(Card::parse_data("JOKER"), Ok((Rank::Joker, Suit::Joker)));
+    }
+
+    #[test]
+    fn parse_invalid_cards() {
+        assert!(Card::parse_data("").is_err());
+        assert!(Card::parse_data("A").is_err());
+        assert!(Card::parse_data("AX").is_err());
+        assert!(Card::parse_data("11H").is_err());
+        assert!(Card::parse_data("ÅS").is_err());
+        assert!(Card::parse_data("JOKERS").is_err());
+    }
+
+    #[test]
+    fn display_parses_back() {
+        let deck = full_deck();
+        for card in deck.get_cards() {
+            assert_eq!(Card::parse_data(&card.to_string()), Ok(card.data()));
+        }
+    }
+
+    #[test]
+    fn display_mixed_joker_as_joker() {
+        let card = Card::new(Rc::new(full_deck()), Rank::Ace, Suit::Joker);
+        assert_eq!(card.to_string(), "Joker");
+    }
+
+    #[test]
+    fn byte_round_trip() {
+        let deck = Rc::new(full_deck());
+        for card in deck.get_cards() {
+            let decoded = Card::from_byte(card.to_byte(), deck.clone());
+            assert_eq!(decoded.map(|card| card.data()), Ok(card.data()));
+        }
+    }
+
+    #[test]
+    fn from_byte_rejects_invalid_bytes() {
+        let deck = Rc::new(full_deck());
+        let mixed_jokers = [
+            ((Rank::Joker as u8) << 4) | Suit::Spades as u8,
+            ((Rank::Ace as u8) << 4) | Suit::Joker as u8
+        ];
+        for byte in mixed_jokers {
+            assert!(Card::from_byte(byte, deck.clone()).is_err());
+        }
+        // out of range rank, then suit
+        assert!(Card::from_byte(0xE1, deck.clone()).is_err());
+        assert!(Card::from_byte(0x15, deck).is_err());
+
+        // a Joker, for a deck without Jokers
+        let deck = Rc::new(Deck::new(DeckConfig::default()).unwrap());
+        assert!(Card::from_byte(0, deck).is_err());
+    }
+}
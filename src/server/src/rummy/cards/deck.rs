@@ -2,7 +2,9 @@ use std::rc::Rc;
 
 use super::card::Card;
 use super::suit_rank::{Rank, Suit};
-use rand::seq::SliceRandom;
+use crate::rummy::game::error::GameError;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Serialize, Deserialize};
 use strum::IntoEnumIterator;
 
 /// Configurable parameters for a deck:
@@ -10,11 +12,105 @@ use strum::IntoEnumIterator;
 /// - `use_joker`: Whether to add Jokers and use them as wildcard (2 per pack)
 /// - `high_rank`: Whether to override the highest rank (default being King)
 /// - `wildcard_rank`: Whether to have a wildcard rank (mutually exclusive with `use_joker`)
+/// - `ace_behavior`: Whether Ace is low (A-2-3), high (Q-K-A) or both in runs, regardless of `high_rank`
+/// - `max_wildcards_per_meld`: Whether to limit the number of wildcards in a meld
+/// - `shuffle_seed`: Whether to seed the deck's shuffling, making every shuffle reproducible (never serialized, so it stays on the server)
+/// - `wildcard_end_policy`: Whether wildcards may extend the ends of a run, or only fill gaps inside it
+/// - `discard_face_down`: Whether the discard pile is face-down, so its top card can't be seen (but can still be drawn)
+/// - `min_meld_size`: The minimum number of cards in a set or run (usually 3)
+/// 
+/// **Note**: As fields keep being added, this is `non_exhaustive`; outside this crate,
+/// start from `DeckConfig::default()` and set the fields you need.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct DeckConfig {
     pub pack_count: usize,
     pub use_joker: bool,
     pub high_rank: Option<Rank>,
-    pub wildcard_rank: Option<Rank>
+    pub wildcard_rank: Option<Rank>,
+    pub ace_behavior: AceBehavior,
+    pub max_wildcards_per_meld: Option<usize>,
+    #[serde(skip)]
+    pub shuffle_seed: Option<u64>,
+    pub wildcard_end_policy: WildcardEndPolicy,
+    pub discard_face_down: bool,
+    pub min_meld_size: usize
+}
+
+/// Number of non-Joker cards in a pack.
+const CARDS_PER_PACK: usize = 52;
+
+/// Number of Jokers in a pack (if `use_joker` is set).
+const JOKERS_PER_PACK: usize = 2;
+
+impl DeckConfig {
+    /// Get the number of cards a fresh deck would contain under this config.
+    pub fn deck_size(&self) -> usize {
+        let pack_size = if self.use_joker { CARDS_PER_PACK + JOKERS_PER_PACK } else { CARDS_PER_PACK };
+        pack_size * self.pack_count
+    }
+
+    /// Checks that the config's settings are consistent with each other.
+    /// 
+    /// Returns `Err` if:
+    /// - `pack_count` < 1
+    /// - `use_joker` is true while `wildcard_rank` isn't `None`
+    /// - `max_wildcards_per_meld` is set while there are no wildcards (neither Jokers nor a `wildcard_rank`)
+    /// - `min_meld_size` < 1
+    pub fn validate(&self) -> Result<(), GameError> {
+        if self.pack_count < 1 {
+            return Err(GameError::InvalidConfig("Pack count < 1 while instantiating a Deck".to_owned()));
+        }
+        if self.wildcard_rank.is_some() && self.use_joker {
+            return Err(GameError::InvalidConfig("Cannot use Joker and specify a wildcard in a Deck".to_owned()));
+        }
+        if self.max_wildcards_per_meld.is_some() && self.wildcard_rank.is_none() && !self.use_joker {
+            return Err(GameError::InvalidConfig("Cannot limit wildcards per meld without any wildcards in a Deck".to_owned()));
+        }
+        if self.min_meld_size < 1 {
+            return Err(GameError::InvalidConfig("Minimum meld size < 1 while instantiating a Deck".to_owned()));
+        }
+        Ok(())
+    }
+}
+
+/// Defaults to a single pack of 52 cards with no wildcards, with Ace low and King high.
+impl Default for DeckConfig {
+    fn default() -> Self {
+        DeckConfig {
+            pack_count: 1,
+            use_joker: false,
+            high_rank: None,
+            wildcard_rank: None,
+            ace_behavior: AceBehavior::Low,
+            max_wildcards_per_meld: None,
+            shuffle_seed: None,
+            wildcard_end_policy: WildcardEndPolicy::Allow,
+            discard_face_down: false,
+            min_meld_size: 3
+        }
+    }
+}
+
+/// Where Ace may be placed in a run:
+/// - Low: Only before Two (ie A-2-3)
+/// - High: Only after King (ie Q-K-A)
+/// - Both: Either of the above (but not wrapping around, ie K-A-2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AceBehavior {
+    Low,
+    High,
+    Both
+}
+
+/// Where wildcards may be placed in a run:
+/// - Allow: Anywhere there's a rank for it to stand in for, including past either end of its natural cards
+///   (ie 5-6-Wild, or Wild-Q-K if Ace is low)
+/// - Deny: Only filling gaps between its natural cards (ie 5-Wild-7, but not Wild-5-6 or 5-6-Wild)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WildcardEndPolicy {
+    Allow,
+    Deny
 }
 
 // TODO: verify cards belong to the deck before adding to discard pile
@@ -23,29 +119,39 @@ pub struct DeckConfig {
 /// The deck, consisting of the:
 /// - **stock**, face-down cards that can be drawn at the start of each turn
 /// - **discard pile**, discarded cards, which can also be drawn
+/// 
+/// It also keeps a **discard history** of every card discarded this round,
+/// which isn't affected by drawing from the discard pile.
+#[derive(Clone)]
 pub struct Deck {
     config: DeckConfig,
     stock: Vec<Card>,
-    discard_pile: Vec<Card>
+    discard_pile: Vec<Card>,
+    discard_history: Vec<(Rank, Suit)>,
+    reshuffle_count: usize,
+    rng: StdRng
 }
 
 impl Deck {
     /// Creates a new deck following settings in `config`.
     /// 
-    /// **Note**: Returns `Err` if `pack_count` < 1, or `use_joker` is true while `wildcard_rank` isn't `None`.
+    /// **Note**: Returns `Err` if `config` is inconsistent (see `DeckConfig::validate`).
     /// TODO: why can't I make this pub(crate) without angering basic.rs?
-    pub(crate) fn new(config: DeckConfig) -> Result<Self, String> {
-        if config.pack_count < 1 {
-            return Err("Pack count < 1 while instantiating a Deck".to_owned());
-        }
-        if config.wildcard_rank.is_some() && config.use_joker {
-            return Err("Cannot use Joker and specify a wildcard in a Deck".to_owned());
-        }
+    pub(crate) fn new(config: DeckConfig) -> Result<Self, GameError> {
+        config.validate()?;
+
+        let rng = match config.shuffle_seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy()
+        };
 
         let mut deck = Deck {
             config,
             stock: Vec::new(),
-            discard_pile: Vec::new()
+            discard_pile: Vec::new(),
+            discard_history: Vec::new(),
+            reshuffle_count: 0,
+            rng
         };
 
         for i in 0..config.pack_count {
@@ -57,25 +163,32 @@ impl Deck {
                 }
             }
             if config.use_joker {
-                deck.stock.push(Card { rank: Rank::Joker, suit: Suit::Joker, deck: Rc::new(deck) });
+                for _ in 0..JOKERS_PER_PACK {
+                    deck.stock.push(Card { rank: Rank::Joker, suit: Suit::Joker, deck: Rc::new(deck) });
+                }
             }
         }
 
         Ok(deck)
     }
 
-    /// Draw a chosen amount of cards (usually 1) from the deck stock.
+    /// Draw a chosen amount of cards (usually 1) from the top of the deck stock
+    /// (ie the end of `stock`).
     /// 
     /// If the amount is greater than the stock's size, return `Err`.
     /// 
     /// If the deck is empty after drawing, shuffle the discarded cards back into it.
-    pub(crate) fn draw(&mut self, amount: usize) -> Result<Vec<Card>, String> {
+    pub(crate) fn draw(&mut self, amount: usize) -> Result<Vec<Card>, GameError> {
         if amount > self.stock.len() {
-            return Err(format!("Draw amount ({amount}) greater than stock size ({})", self.stock.len()));
+            return Err(GameError::NotEnoughCards { requested: amount, available: self.stock.len() });
         }
 
         let cards = self.stock.split_off(self.stock.len() - amount);
-        if self.stock.len() == 0 {
+        if self.stock_is_empty() {
+            // only count it if there were discarded cards to shuffle back
+            if !self.discard_is_empty() {
+                self.reshuffle_count += 1;
+            }
             self.reset_deck();
         };
         
@@ -83,7 +196,12 @@ impl Deck {
     }
 
     /// See the top card of the discard pile, if there is one.
+    /// 
+    /// If the deck's discard pile is face-down, this is always `None`.
     pub(crate) fn peek_discard_pile(&self) -> Option<(Rank, Suit)> {
+        if self.config.discard_face_down {
+            return None;
+        }
         self.discard_pile
             .last()
             .map(|card| card.data())
@@ -91,18 +209,21 @@ impl Deck {
 
     /// Attempt to draw a chosen amount of cards from the discard pile.
     /// 
-    /// If the amount is greater than discard pile's size, or the discard pile is empty,
+    /// If the amount is 0 or greater than discard pile's size, or the discard pile is empty,
     /// return `Err`.
     /// 
     /// If `None` amount is specified, attempt to draw the entire discard pile.
-    pub(crate) fn draw_discard_pile(&mut self, amount: Option<usize>) -> Result<Vec<Card>, String> {
+    pub(crate) fn draw_discard_pile(&mut self, amount: Option<usize>) -> Result<Vec<Card>, GameError> {
         let discard_size = self.discard_pile.len();
-        if discard_size == 0 {
-            return Err(format!("Can't draw from empty discard pile"));
+        if self.discard_is_empty() {
+            return Err(GameError::EmptyDiscardPile);
         }
         else if let Some(a) = amount {
+            if a == 0 {
+                return Err(GameError::ZeroDrawAmount);
+            }
             if a > discard_size {
-                return Err(format!("Draw amount ({a}) greater than discard pile size ({discard_size})"));
+                return Err(GameError::NotEnoughCards { requested: a, available: discard_size });
             }
             return Ok(
                 self.discard_pile.split_off(discard_size - a)
@@ -113,18 +234,80 @@ impl Deck {
         );
     }
 
+    /// Draw the entire discard pile, keeping only the cards at `keep_indices`
+    /// (where index 0 is the bottom of the pile) and returning the rest to the discard pile.
+    /// 
+    /// The kept cards are returned in pile order,
+    /// and the rest stay in the discard pile in their original relative order.
+    /// 
+    /// If the discard pile is empty, `keep_indices` is empty, or any index is out of bounds,
+    /// return `Err` and leave the discard pile unchanged.
+    pub(crate) fn draw_discard_keep(&mut self, keep_indices: Vec<usize>) -> Result<Vec<Card>, GameError> {
+        let discard_size = self.discard_pile.len();
+        if self.discard_is_empty() {
+            return Err(GameError::EmptyDiscardPile);
+        }
+        if keep_indices.is_empty() {
+            return Err(GameError::ZeroDrawAmount);
+        }
+        if let Some(&index) = keep_indices.iter().find(|&&i| i >= discard_size) {
+            return Err(GameError::InvalidIndex { index, len: discard_size });
+        }
+
+        let (kept, rest): (Vec<(usize, Card)>, Vec<(usize, Card)>) = self.discard_pile
+            .drain(..)
+            .enumerate()
+            .partition(|(i, _)| keep_indices.contains(i));
+        self.discard_pile = rest
+            .into_iter()
+            .map(|(_, card)| card)
+            .collect();
+
+        Ok(
+            kept.into_iter().map(|(_, card)| card).collect()
+        )
+    }
+
     /// Moves cards from `cards` into the discard pile, leaving it empty.
+    /// 
+    /// The cards are also recorded in the discard history.
     pub(crate) fn add_to_discard_pile(&mut self, cards: &mut Vec<Card>) {
+        self.discard_history.extend(cards.iter().map(|card| card.data()));
         self.discard_pile.append(cards);
     }
 
+    /// Moves cards from `cards` to the top of the stock (ie they're drawn next), leaving it empty.
+    /// 
+    /// The last card in `cards` ends up on top.
+    pub(crate) fn return_to_stock_top(&mut self, cards: &mut Vec<Card>) {
+        self.stock.append(cards);
+    }
+
+    /// Moves cards from `cards` to the bottom of the stock (ie they're drawn last), leaving it empty.
+    /// 
+    /// The cards keep their order, so the first card in `cards` ends up at the very bottom.
+    pub(crate) fn return_to_stock_bottom(&mut self, cards: &mut Vec<Card>) {
+        self.stock.splice(0..0, cards.drain(..));
+    }
+
+    /// Clears the discard history.
+    /// 
+    /// Should be called when a round ends; it isn't cleared by `reset_deck`,
+    /// as that's also called when the stock runs out mid-round.
+    pub(crate) fn clear_discard_history(&mut self) {
+        self.discard_history.clear();
+    }
+
     /// Reset the stock by moving the discard pile into it and shuffling.
     /// 
+    /// If the deck has a `shuffle_seed`, the shuffle is deterministic
+    /// (ie the same sequence of resets always produces the same stocks).
+    /// 
     /// Typically called when stock is emptied during gameplay,
     /// or when starting a new round (and all player cards have been discarded).
     pub(crate) fn reset_deck(&mut self) {
         self.stock.append(&mut self.discard_pile);
-        self.stock.shuffle(&mut rand::thread_rng());
+        self.stock.shuffle(&mut self.rng);
     }
 }
 
@@ -141,13 +324,318 @@ impl Deck {
     }
 
     /// Get a reference to the deck discard pile.
+    /// 
+    /// **Note**: This ignores `discard_face_down`, so it shouldn't be shown to players as-is.
     pub(crate) fn get_discard_pile(&self) -> &Vec<Card> {
         &self.discard_pile
     }
+
+    /// Get a reference to the discard history, ordered from first to last discarded.
+    /// 
+    /// **Note**: This ignores `discard_face_down`, so it shouldn't be shown to players as-is.
+    pub(crate) fn get_discard_history(&self) -> &Vec<(Rank, Suit)> {
+        &self.discard_history
+    }
+
+    /// Get the seed the deck's shuffling was created with, if there is one.
+    /// 
+    /// **Note**: The seed reveals every reshuffle of the stock, so it shouldn't be shown to players.
+    pub(crate) fn shuffle_seed(&self) -> Option<u64> {
+        self.config.shuffle_seed
+    }
+
+    /// Get the number of times the stock ran out and the discard pile was shuffled back into it.
+    pub fn reshuffle_count(&self) -> usize {
+        self.reshuffle_count
+    }
+
+    /// Get whether the stock is empty.
+    pub fn stock_is_empty(&self) -> bool {
+        self.stock.is_empty()
+    }
+
+    /// Get whether the discard pile is empty.
+    pub fn discard_is_empty(&self) -> bool {
+        self.discard_pile.is_empty()
+    }
+
+    /// Get the total number of cards in the stock and discard pile.
+    pub fn total_cards(&self) -> usize {
+        self.stock.len() + self.discard_pile.len()
+    }
+
+    /// Count the cards of a given rank remaining in the stock.
+    /// 
+    /// **Note**: The stock is face-down, so this shouldn't be shown to players;
+    /// it's only meant for debugging and simulation.
+    pub fn count_rank_in_stock(&self, rank: Rank) -> usize {
+        self.stock
+            .iter()
+            .filter(|card| card.rank == rank)
+            .count()
+    }
+
+    /// Count the cards of a given suit remaining in the stock.
+    /// 
+    /// **Note**: The stock is face-down, so this shouldn't be shown to players;
+    /// it's only meant for debugging and simulation.
+    pub fn count_suit_in_stock(&self, suit: Suit) -> usize {
+        self.stock
+            .iter()
+            .filter(|card| card.suit == suit)
+            .count()
+    }
+
+    /// Iterate over the rank and suit of each card in the stock, from bottom to top.
+    /// 
+    /// **Note**: The stock is face-down, so this shouldn't be shown to players;
+    /// it's only meant for debugging and simulation.
+    pub fn stock_iter(&self) -> impl Iterator<Item = (Rank, Suit)> + '_ {
+        self.stock.iter().map(|card| card.data())
+    }
+
+    /// Iterate over the rank and suit of each card in the discard pile, from bottom to top.
+    /// 
+    /// If the deck's discard pile is face-down, this yields nothing (like `peek_discard_pile`).
+    pub fn discard_iter(&self) -> impl Iterator<Item = (Rank, Suit)> + '_ {
+        let visible_cards = if self.config.discard_face_down { &self.discard_pile[..0] } else { &self.discard_pile[..] };
+        visible_cards.iter().map(|card| card.data())
+    }
 }
 
+/// An empty deck with the default config.
+/// 
+/// This is needed for deserializing a `Card`, which skips its deck;
+/// the card should then be tied back to its actual deck.
 impl Default for Deck {
     fn default() -> Self {
-        todo!()
+        Deck {
+            config: DeckConfig::default(),
+            stock: Vec::new(),
+            discard_pile: Vec::new(),
+            discard_history: Vec::new(),
+            reshuffle_count: 0,
+            rng: StdRng::from_entropy()
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a deck with `config`, moving the cards with the given ranks (in Hearts) from the stock to the discard pile.
+    fn deck_with_discards(config: DeckConfig, ranks: &[Rank]) -> Deck {
+        let mut deck = Deck::new(config).unwrap();
+        let mut discards: Vec<Card> = ranks
+            .iter()
+            .map(|&rank| {
+                let i = deck.stock.iter().position(|card| card.data() == (rank, Suit::Hearts)).unwrap();
+                deck.stock.remove(i)
+            })
+            .collect();
+        deck.add_to_discard_pile(&mut discards);
+        deck
+    }
+
+    #[test]
+    fn face_down_discard_is_hidden_but_drawable() {
+        let config = DeckConfig { discard_face_down: true, ..DeckConfig::default() };
+        let mut deck = deck_with_discards(config, &[Rank::Two, Rank::Three]);
+
+        assert_eq!(deck.peek_discard_pile(), None);
+        assert_eq!(deck.discard_iter().count(), 0);
+
+        let drawn = deck.draw_discard_pile(Some(1)).unwrap();
+        assert_eq!(drawn[0].data(), (Rank::Three, Suit::Hearts));
+    }
+
+    #[test]
+    fn face_up_discard_is_visible() {
+        let mut deck = deck_with_discards(DeckConfig::default(), &[Rank::Two, Rank::Three]);
+
+        assert_eq!(deck.peek_discard_pile(), Some((Rank::Three, Suit::Hearts)));
+        assert_eq!(
+            deck.discard_iter().collect::<Vec<_>>(),
+            vec![(Rank::Two, Suit::Hearts), (Rank::Three, Suit::Hearts)]
+        );
+        assert!(deck.draw_discard_pile(Some(1)).is_ok());
+    }
+
+    #[test]
+    fn draw_discard_keep_buried_card() {
+        let mut deck = deck_with_discards(DeckConfig::default(), &[Rank::Two, Rank::Three, Rank::Four, Rank::Five]);
+
+        let kept = deck.draw_discard_keep(vec![1]).unwrap();
+        assert_eq!(kept.iter().map(|card| card.data()).collect::<Vec<_>>(), vec![(Rank::Three, Suit::Hearts)]);
+        assert_eq!(
+            deck.discard_iter().collect::<Vec<_>>(),
+            vec![(Rank::Two, Suit::Hearts), (Rank::Four, Suit::Hearts), (Rank::Five, Suit::Hearts)]
+        );
+    }
+
+    #[test]
+    fn draw_discard_keep_invalid_indices() {
+        let mut deck = deck_with_discards(DeckConfig::default(), &[Rank::Two, Rank::Three]);
+
+        assert!(matches!(deck.draw_discard_keep(vec![]), Err(GameError::ZeroDrawAmount)));
+        assert!(matches!(deck.draw_discard_keep(vec![0, 2]), Err(GameError::InvalidIndex { index: 2, len: 2 })));
+        assert_eq!(deck.discard_iter().count(), 2);
+
+        let mut deck = deck_with_discards(DeckConfig::default(), &[]);
+        assert!(matches!(deck.draw_discard_keep(vec![0]), Err(GameError::EmptyDiscardPile)));
+    }
+
+    #[test]
+    fn config_json_round_trip() {
+        let config = DeckConfig {
+            ace_behavior: AceBehavior::Both,
+            wildcard_rank: Some(Rank::Two),
+            min_meld_size: 4,
+            ..DeckConfig::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<DeckConfig>(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn discard_history_survives_draws() {
+        let ranks = [Rank::Two, Rank::Three, Rank::Four, Rank::Five];
+        let mut deck = deck_with_discards(DeckConfig::default(), &ranks);
+        let history: Vec<(Rank, Suit)> = ranks.iter().map(|&rank| (rank, Suit::Hearts)).collect();
+
+        deck.draw_discard_pile(Some(1)).unwrap();
+        deck.draw_discard_keep(vec![0]).unwrap();
+        assert_eq!(deck.get_discard_history(), &history);
+
+        deck.clear_discard_history();
+        assert!(deck.get_discard_history().is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn stock_iter_bottom_to_top() {
+        let mut deck = Deck::new(DeckConfig::default()).unwrap();
+        let stock: Vec<(Rank, Suit)> = deck.stock_iter().collect();
+        assert_eq!(stock.len(), 52);
+
+        let drawn = deck.draw(1).unwrap();
+        assert_eq!(Some(&drawn[0].data()), stock.last());
+        assert_eq!(deck.stock_iter().collect::<Vec<_>>(), stock[..51]);
+    }
+
+    #[test]
+    fn emptiness_and_total_cards() {
+        let mut deck = Deck::new(DeckConfig::default()).unwrap();
+        assert!(!deck.stock_is_empty());
+        assert!(deck.discard_is_empty());
+        assert_eq!(deck.total_cards(), 52);
+
+        let mut cards = deck.draw(2).unwrap();
+        assert_eq!(deck.total_cards(), 50);
+        deck.add_to_discard_pile(&mut cards);
+        assert!(!deck.discard_is_empty());
+        assert_eq!(deck.total_cards(), 52);
+    }
+
+    #[test]
+    fn same_seed_shuffles_the_same() {
+        let config = DeckConfig { shuffle_seed: Some(42), ..DeckConfig::default() };
+        let mut decks = [Deck::new(config.clone()).unwrap(), Deck::new(config).unwrap()];
+
+        for _ in 0..3 {
+            for deck in &mut decks {
+                let mut cards = deck.draw(20).unwrap();
+                deck.add_to_discard_pile(&mut cards);
+                deck.reset_deck();
+            }
+            assert_eq!(
+                decks[0].stock_iter().collect::<Vec<_>>(),
+                decks[1].stock_iter().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn shuffle_seed_is_not_serialized() {
+        let config = DeckConfig { shuffle_seed: Some(42), ..DeckConfig::default() };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<DeckConfig>(&json).unwrap().shuffle_seed, None);
+    }
+
+    #[test]
+    fn return_to_stock_top_and_bottom() {
+        let mut deck = Deck::new(DeckConfig::default()).unwrap();
+
+        let mut card = deck.draw(1).unwrap();
+        let card_data = card[0].data();
+        deck.return_to_stock_bottom(&mut card);
+        assert!(card.is_empty());
+        assert_ne!(deck.draw(1).unwrap()[0].data(), card_data);
+        assert_eq!(deck.stock_iter().next(), Some(card_data));
+
+        let mut card = deck.draw(1).unwrap();
+        let card_data = card[0].data();
+        deck.return_to_stock_top(&mut card);
+        assert!(card.is_empty());
+        assert_eq!(deck.draw(1).unwrap()[0].data(), card_data);
+    }
+
+    #[test]
+    fn reshuffle_count_only_counts_reshuffles() {
+        let mut deck = Deck::new(DeckConfig::default()).unwrap();
+
+        let mut discards = deck.draw(2).unwrap();
+        deck.add_to_discard_pile(&mut discards);
+        let mut hand = deck.draw(50).unwrap();
+        assert_eq!(deck.reshuffle_count(), 1);
+
+        let mut discards: Vec<Card> = hand.drain(..10).collect();
+        deck.add_to_discard_pile(&mut discards);
+        deck.draw(2).unwrap();
+        assert_eq!(deck.reshuffle_count(), 2);
+
+        // draining with nothing discarded doesn't count
+        deck.draw(10).unwrap();
+        assert!(deck.stock_is_empty());
+        deck.draw(0).unwrap();
+        assert_eq!(deck.reshuffle_count(), 2);
+    }
+
+    #[test]
+    fn draw_zero_from_discard_pile() {
+        let mut deck = deck_with_discards(DeckConfig::default(), &[Rank::Two, Rank::Three]);
+
+        assert!(matches!(deck.draw_discard_pile(Some(0)), Err(GameError::ZeroDrawAmount)));
+        assert_eq!(
+            deck.discard_iter().collect::<Vec<_>>(),
+            vec![(Rank::Two, Suit::Hearts), (Rank::Three, Suit::Hearts)]
+        );
+    }
+
+    #[test]
+    fn counts_in_fresh_stock() {
+        let deck = Deck::new(DeckConfig::default()).unwrap();
+        for rank in Rank::iter().filter(|&rank| rank != Rank::Joker) {
+            assert_eq!(deck.count_rank_in_stock(rank), 4);
+        }
+        for suit in Suit::iter().filter(|&suit| suit != Suit::Joker) {
+            assert_eq!(deck.count_suit_in_stock(suit), 13);
+        }
+        assert_eq!(deck.count_rank_in_stock(Rank::Joker), 0);
+    }
+
+    #[test]
+    fn deck_size_matches_new_deck() {
+        let configs = [
+            (DeckConfig::default(), 52),
+            (DeckConfig { pack_count: 2, ..DeckConfig::default() }, 104),
+            (DeckConfig { use_joker: true, ..DeckConfig::default() }, 54),
+            (DeckConfig { pack_count: 2, use_joker: true, ..DeckConfig::default() }, 108)
+        ];
+        for (config, size) in configs {
+            assert_eq!(config.deck_size(), size);
+            assert_eq!(Deck::new(config).unwrap().total_cards(), size);
+        }
+    }
+}
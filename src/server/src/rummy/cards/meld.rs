@@ -1,14 +1,113 @@
-use super::{card::Card, suit_rank::Rank};
+use std::rc::Rc;
+use serde::{Serialize, Deserialize};
+use super::{card::Card, deck::{AceBehavior, Deck, WildcardEndPolicy}, suit_rank::{Rank, Suit}};
+use crate::rummy::game::error::GameError;
 
 /// A Rummy meld.
 /// 
 /// There are 2 types: a **set** (>=3 cards of same rank),
 /// and **run** (>=3 sequential cards of same suit).
+/// 
+/// The minimum size of 3 can be changed with the deck's `min_meld_size`.
+/// 
+/// **NOTE**: As cards' decks aren't serialized, `set_deck` must be called after deserializing a meld.
+#[derive(Serialize, Deserialize)]
 pub enum Meld {
     Set(Set),
     Run(Run)
 }
 
+impl Meld {
+    /// Attempt to form a meld out of `cards`; as a set if possible, else as a run.
+    /// 
+    /// If neither is valid, `Error` is returned along with the cards.
+    pub fn new(cards: Vec<Card>) -> Result<Self, Vec<Card>> {
+        Meld::new_set(cards).or_else(Meld::new_run)
+    }
+
+    /// Attempt to form a set out of `cards`.
+    /// 
+    /// If it isn't valid, `Error` is returned along with the cards (even if they'd form a valid run).
+    pub fn new_set(cards: Vec<Card>) -> Result<Self, Vec<Card>> {
+        Set::new(cards).map(Meld::Set)
+    }
+
+    /// Attempt to form a run out of `cards`.
+    /// 
+    /// If it isn't valid, `Error` is returned along with the cards (even if they'd form a valid set).
+    pub fn new_run(cards: Vec<Card>) -> Result<Self, Vec<Card>> {
+        Run::new(cards).map(Meld::Run)
+    }
+
+    /// Returns whether the meld would still be valid if formed now,
+    /// ie against its cards' current deck configuration.
+    /// 
+    /// Useful if a meld is kept while the configuration changes (ie the wildcard rank between rounds).
+    pub fn is_still_valid(&self) -> bool {
+        match self {
+            Meld::Set(set) => Set::new(set.cards.clone()).is_ok(),
+            Meld::Run(run) => Run::new(run.cards.clone()).is_ok()
+        }
+    }
+
+    /// Returns whether `card` could be laid off to this meld, without moving it.
+    pub fn can_layoff(&self, card: &Card) -> bool {
+        match self {
+            Meld::Set(set) => {
+                let mut cards = set.cards.clone();
+                cards.push(card.clone());
+                Set::new(cards).is_ok()
+            },
+            Meld::Run(run) => {
+                let mut cards = run.cards.clone();
+                cards.push(card.clone());
+                Run::new(cards).is_ok()
+            }
+        }
+    }
+
+    /// Attempt to layoff `card` to this meld, recording `player_id` as the player who laid it off.
+    /// 
+    /// If the card doesn't fit, `Error` is returned along with the card and the reason it doesn't fit.
+    pub(crate) fn layoff_card(&mut self, card: Card, player_id: usize) -> Result<(), (Card, GameError)> {
+        let card_data = card.data();
+        let layoffs = match self {
+            Meld::Set(set) => {
+                set.try_add_card(card)?;
+                &mut set.layoffs
+            },
+            Meld::Run(run) => {
+                run.try_add_card(card)?;
+                &mut run.layoffs
+            }
+        };
+        layoffs.push((card_data, player_id));
+        Ok(())
+    }
+
+    /// Ties each of the meld's cards to `deck`.
+    /// 
+    /// As a card's deck is skipped by serde, this must be called after deserializing the meld.
+    pub(crate) fn set_deck(&mut self, deck: Rc<Deck>) {
+        let cards = match self {
+            Meld::Set(set) => &mut set.cards,
+            Meld::Run(run) => &mut run.cards
+        };
+        cards
+            .iter_mut()
+            .for_each(|card| card.set_deck(deck.clone()));
+    }
+
+    /// Get the cards laid off to this meld after it was formed,
+    /// each with the ID of the player who laid it off.
+    pub fn layoffs(&self) -> &Vec<((Rank, Suit), usize)> {
+        match self {
+            Meld::Set(set) => &set.layoffs,
+            Meld::Run(run) => &run.layoffs
+        }
+    }
+}
+
 pub trait Meldable {
     /// Attempt to create a new meld out of a Vec of `Card`s.
     /// 
@@ -22,76 +121,76 @@ pub trait Meldable {
     /// Attempt to add a `Card` to the set.
     /// 
     /// If the new card fits into the meld, it is moved into the meld and `Ok` is returned.
-    /// Else, `Error` is returned along with the card.
+    /// Else, `Error` is returned along with the card and the reason it doesn't fit.
     /// 
     /// **NOTE**: in the `Error` case, the caller must ensure the card is moved somewhere concrete,
     /// like a deck/player hand/discard pile.
-    fn try_add_card(&mut self, card: Card) -> Result<(), Card>;
+    fn try_add_card(&mut self, card: Card) -> Result<(), (Card, GameError)>;
+}
+
+/// Returns whether a potential meld has at least the deck's `min_meld_size` cards.
+fn has_valid_size(cards: &[Card]) -> bool {
+    // TODO: do I just assume that every card is tied to the same deck?
+    !cards.is_empty() && cards.len() >= cards[0].deck.get_config().min_meld_size
+}
+
+/// Returns whether a potential meld has an allowed amount of wildcards;
+/// it must have at least 1 non-wildcard card,
+/// and no more wildcards than the deck's `max_wildcards_per_meld` (if there is one).
+fn has_valid_wildcard_count(cards: &[Card]) -> bool {
+    let deck_config = cards[0].deck.get_config();
+    let wildcard_count = cards.iter().filter(|card| card.is_wildcard()).count();
+
+    wildcard_count < cards.len()
+    && deck_config.max_wildcards_per_meld.is_none_or(|max| wildcard_count <= max)
 }
 
 
 /// A Rummy meld set.
+#[derive(Serialize, Deserialize)]
 pub struct Set {
     cards: Vec<Card>,
-    pub(crate) set_rank: Rank
+    pub(crate) set_rank: Rank,
+    pub(crate) layoffs: Vec<((Rank, Suit), usize)>
 }
 
 impl Meldable for Set {
-    fn new(mut cards: Vec<Card>) -> Result<Self, Vec<Card>> {
-        // TODO: do I just assume that every card is tied to the same deck?
-        match cards[0].deck.get_config().wildcard_rank {
-            // every card has same rank, or is a wildcard.
-            Some(wildcard_rank) => {
-                let mut set_rank: Option<Rank> = None;
-                if cards
-                    .iter()
-                    .all(|card| {
-                        if card.rank == wildcard_rank { return true; }
-                        else {
-                            match set_rank {
-                                Some(rank) => return card.rank == rank,
-                                None => {
-                                    set_rank = Some(card.rank);
-                                    return true;
-                                }
-                            }
-                        }
-                    }) {
-                    if let Some(set_rank) = set_rank {
-                        return Ok(Set{set_rank, cards});
-                    }
-                    else { // means no non-wildcard, which we don't want to allow
-                        return Err(cards);
-                    }
-                }
-                else {
-                    return Err(cards);
-                }
-                
-            },
-            // every card has same rank.
-            None => {
-                if cards
-                    .iter()
-                    .all(|card| card.rank == cards[0].rank) {
-                    return Ok(Set{set_rank: cards[0].rank, cards});
-                }   
-                else {
-                    return Err(cards);
-                }
-            }
+    fn new(cards: Vec<Card>) -> Result<Self, Vec<Card>> {
+        if !has_valid_size(&cards) || !has_valid_wildcard_count(&cards) {
+            return Err(cards);
         }
-    }
 
-    fn try_add_card(&mut self, card: Card) -> Result<(), Card> {
-        if card.rank != self.set_rank { 
-            return Err(card); 
+        // every card has same rank, or is a wildcard.
+        let set_rank = match cards.iter().find(|card| !card.is_wildcard()) {
+            Some(card) => card.rank,
+            None => return Err(cards) // means no non-wildcard, which we don't want to allow
+        };
+        if cards
+            .iter()
+            .all(|card| card.is_wildcard() || card.rank == set_rank) {
+            Ok(Set{set_rank, cards, layoffs: Vec::new()})
+        }
+        else {
+            Err(cards)
         }
-        else if let Some(wildcard_rank) = card.deck.get_config().wildcard_rank {
-            if card.rank != wildcard_rank {
-                return Err(card);
+    }
+
+    fn try_add_card(&mut self, card: Card) -> Result<(), (Card, GameError)> {
+        let max_wildcards = card.deck.get_config().max_wildcards_per_meld;
+
+        if card.is_wildcard() {
+            let wildcard_count = self.cards
+                .iter()
+                .filter(|card| card.is_wildcard())
+                .count();
+            if max_wildcards.is_some_and(|max| wildcard_count >= max) {
+                return Err((card, GameError::TooManyWildcards));
             }
         }
+        else if card.rank != self.set_rank {
+            let actual = card.rank;
+            return Err((card, GameError::WrongRank { expected: self.set_rank, actual }));
+        }
         self.cards.push(card);
         Ok(())
     }
@@ -99,55 +198,473 @@ impl Meldable for Set {
 
 
 /// A Rummy meld run.
+#[derive(Serialize, Deserialize)]
 pub struct Run {
-    pub(crate) cards: Vec<Card>
+    pub(crate) cards: Vec<Card>,
+    pub(crate) layoffs: Vec<((Rank, Suit), usize)>
 }
 
 impl Meldable for Run {
-    fn new(mut cards: Vec<Card>) -> Result<Self, Vec<Card>> {
-        // TODO: any way to not do this?
-        let backup_cards = cards.clone();
+    fn new(cards: Vec<Card>) -> Result<Self, Vec<Card>> {
+        if !has_valid_size(&cards) || !has_valid_wildcard_count(&cards) {
+            return Err(cards);
+        }
 
         // TODO: do I just assume that every card is tied to the same deck?
-        let deck_config = cards[0].deck.get_config();
+        let ace_behavior = cards[0].deck.get_config().ace_behavior;
+
+        // Ace behaviour is independent of the deck's high rank,
+        // so we order the run by our own rank values instead of `Card::cmp`.
+        let cards = match ace_behavior {
+            AceBehavior::Low => Run::order_cards(cards, false),
+            AceBehavior::High => Run::order_cards(cards, true),
+            AceBehavior::Both => Run::order_cards(cards, false)
+                .or_else(|cards| Run::order_cards(cards, true))
+        }?;
 
-        let mut wildcards = match deck_config.wildcard_rank {
-            Some(wildcard_rank) => {
-                cards.iter().filter(|&card| card.rank == wildcard_rank).collect()
+        Ok(Run { cards, layoffs: Vec::new() })
+    }
+
+    fn try_add_card(&mut self, card: Card) -> Result<(), (Card, GameError)> {
+        // TODO: any way to not do this?
+        let mut cards = self.cards.clone();
+        cards.push(card);
+
+        match Run::new(cards) {
+            Ok(run) => {
+                self.cards = run.cards;
+                Ok(())
             },
-            None => {
-                Vec::new()
+            Err(mut cards) => {
+                let card = cards.pop().unwrap(); // the card we pushed is still last
+                let err = self.layoff_error(&card);
+                Err((card, err))
             }
+        }
+    }
+}
+
+impl Run {
+    /// Works out why `card` couldn't be laid off to this run.
+    fn layoff_error(&self, card: &Card) -> GameError {
+        let deck_config = card.deck.get_config();
+        let is_wildcard = card.is_wildcard();
+
+        let wildcard_count = self.cards
+            .iter()
+            .filter(|card| card.is_wildcard())
+            .count();
+        let run_suit = self.cards
+            .iter()
+            .find(|card| !card.is_wildcard())
+            .map(|card| card.suit)
+            .unwrap(); // every run has a non-wildcard card
+
+        if !is_wildcard && card.suit != run_suit {
+            GameError::WrongSuit { expected: run_suit, actual: card.suit }
+        }
+        else if is_wildcard && deck_config.max_wildcards_per_meld.is_some_and(|max| wildcard_count >= max) {
+            GameError::TooManyWildcards
+        }
+        else if self.cards.len() >= Rank::King as usize { // a run can't hold more than 13 ranks
+            GameError::MeldFull
+        }
+        else {
+            GameError::NotAdjacent
+        }
+    }
+
+    /// Attempt to order `cards` into a valid run,
+    /// treating Ace as the rank after King if `ace_high` is set (else as the rank before Two).
+    /// 
+    /// Wildcards are used to fill any gaps, and leftover wildcards are placed past the top of the run,
+    /// or below its bottom once the top is the highest rank (unless the deck's `wildcard_end_policy` denies this).
+    /// 
+    /// If the cards can't form a run, `Err` is returned with the cards in their original order.
+    fn order_cards(mut cards: Vec<Card>, ace_high: bool) -> Result<Vec<Card>, Vec<Card>> {
+        // TODO: any way to not do this?
+        let backup_cards = cards.clone();
+
+        let wildcard_end_policy = cards[0].deck.get_config().wildcard_end_policy;
+        let rank_value = |card: &Card| {
+            if ace_high && card.rank == Rank::Ace { Rank::King as u8 + 1 }
+            else { card.rank as u8 }
         };
-        
-        cards.sort();
-
-        // Check that each card is same suit and +1 rank from previous card (or previous card is wildcard).
-        // If not, try to insert a wildcard; if we have none, return Error with the backup cards.
-        for i in 1..cards.len() {
-            if cards[i-1].suit == cards[i].suit
-            && cards[i-1].rank as u8 == cards[i+1].rank as u8 + 1 {
-                continue;
-            }
-            else {
-                if let Some(wildcard_rank) = deck_config.wildcard_rank {
-                    if cards[i-1].rank == wildcard_rank {
-                        continue;
-                    }
-                    else if wildcards.len() > 0 {
-                        let &wildcard = wildcards.pop().unwrap();
-                        cards.insert(i, wildcard);
-                        continue;
-                    }
-                } 
-                return Err(backup_cards);
+        let (min_value, max_value) = if ace_high { (Rank::Two as u8, Rank::King as u8 + 1) } 
+            else { (Rank::Ace as u8, Rank::King as u8) };
+
+        let (mut wildcards, mut naturals): (Vec<Card>, Vec<Card>) = cards
+            .drain(..)
+            .partition(|card| card.is_wildcard());
+
+        // need at least 1 natural card to know the run's suit
+        if naturals.is_empty() || naturals.iter().any(|card| card.suit != naturals[0].suit) {
+            return Err(backup_cards);
+        }
+        naturals.sort_by_key(rank_value);
+        let (bottom_value, top_value) = (rank_value(&naturals[0]), rank_value(&naturals[naturals.len() - 1]));
+
+        // Check that each card is +1 rank from the previous card.
+        // If not, try to fill the gap with wildcards; if we don't have enough, return Error with the backup cards.
+        for card in naturals {
+            if let Some(prev_card) = cards.last() {
+                let (prev_value, value) = (rank_value(prev_card), rank_value(&card));
+                if value <= prev_value { 
+                    return Err(backup_cards);
+                }
+                let gap = (value - prev_value - 1) as usize;
+                if gap > wildcards.len() {
+                    return Err(backup_cards);
+                }
+                cards.extend(wildcards.drain(..gap));
             }
+            cards.push(card);
         }
 
-        Ok(Run { cards })
+        // Leftover wildcards go above the top card while there are ranks left there, then below the bottom card;
+        // if they'd go past the lowest rank too, the run would cover more ranks than exist.
+        let high_count = wildcards.len().min((max_value - top_value) as usize);
+        let low_count = wildcards.len() - high_count;
+        if low_count > (bottom_value - min_value) as usize {
+            return Err(backup_cards);
+        }
+
+        // under `Deny`, no wildcard may dangle off either end of the run
+        if wildcard_end_policy == WildcardEndPolicy::Deny && (high_count > 0 || low_count > 0) {
+            return Err(backup_cards);
+        }
+        let mut high_wildcards = wildcards.split_off(low_count);
+        cards.append(&mut high_wildcards);
+        cards.splice(0..0, wildcards);
+
+        Ok(cards)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rummy::cards::deck::DeckConfig;
+
+    /// Creates cards with the given ranks and suits, all tied to a deck made with `config`.
+    fn cards(config: &DeckConfig, data: &[(Rank, Suit)]) -> Vec<Card> {
+        let deck = Rc::new(Deck::new(config.clone()).unwrap());
+        data.iter()
+            .map(|&(rank, suit)| Card::new(deck.clone(), rank, suit))
+            .collect()
+    }
+
+    /// Creates Hearts cards with the given ranks, all tied to a deck made with `config`.
+    fn hearts(config: &DeckConfig, ranks: &[Rank]) -> Vec<Card> {
+        let data: Vec<(Rank, Suit)> = ranks.iter().map(|&rank| (rank, Suit::Hearts)).collect();
+        cards(config, &data)
+    }
+
+    fn ace_config(ace_behavior: AceBehavior) -> DeckConfig {
+        DeckConfig { ace_behavior, ..DeckConfig::default() }
     }
 
-    fn try_add_card(&mut self, card: Card) -> Result<(), Card> {
-        todo!();
+    /// A config with Twos as wildcards.
+    fn wildcard_config(ace_behavior: AceBehavior) -> DeckConfig {
+        DeckConfig { ace_behavior, wildcard_rank: Some(Rank::Two), ..DeckConfig::default() }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn ace_low_only_allows_low_ace() {
+        let config = ace_config(AceBehavior::Low);
+        assert!(Run::new(hearts(&config, &[Rank::Ace, Rank::Two, Rank::Three])).is_ok());
+        assert!(Run::new(hearts(&config, &[Rank::Queen, Rank::King, Rank::Ace])).is_err());
+    }
+
+    #[test]
+    fn ace_high_only_allows_high_ace() {
+        let config = ace_config(AceBehavior::High);
+        assert!(Run::new(hearts(&config, &[Rank::Queen, Rank::King, Rank::Ace])).is_ok());
+        assert!(Run::new(hearts(&config, &[Rank::Ace, Rank::Two, Rank::Three])).is_err());
+    }
+
+    #[test]
+    fn ace_both_allows_either_end_without_wrapping() {
+        let config = ace_config(AceBehavior::Both);
+        assert!(Run::new(hearts(&config, &[Rank::Ace, Rank::Two, Rank::Three])).is_ok());
+        assert!(Run::new(hearts(&config, &[Rank::Queen, Rank::King, Rank::Ace])).is_ok());
+        assert!(Run::new(hearts(&config, &[Rank::King, Rank::Ace, Rank::Two])).is_err());
+    }
+
+    #[test]
+    fn ace_behavior_applies_to_layoffs() {
+        let config = ace_config(AceBehavior::Low);
+        let Ok(mut run) = Run::new(hearts(&config, &[Rank::Two, Rank::Three, Rank::Four])) else { panic!("run should be valid") };
+        let mut ace = hearts(&config, &[Rank::Ace]);
+        assert!(run.try_add_card(ace.remove(0)).is_ok());
+
+        let config = ace_config(AceBehavior::High);
+        let Ok(mut run) = Run::new(hearts(&config, &[Rank::Jack, Rank::Queen, Rank::King])) else { panic!("run should be valid") };
+        let mut ace = hearts(&config, &[Rank::Ace]);
+        assert!(run.try_add_card(ace.remove(0)).is_ok());
+    }
+
+    #[test]
+    fn leftover_wildcard_goes_below_highest_rank() {
+        // under Low, Q-K-Wild can only be J-Q-K
+        let config = wildcard_config(AceBehavior::Low);
+        let run_cards = cards(&config, &[(Rank::Queen, Suit::Hearts), (Rank::King, Suit::Hearts), (Rank::Two, Suit::Spades)]);
+        let Ok(run) = Run::new(run_cards) else { panic!("run should be valid") };
+        assert_eq!(run.cards[0].data(), (Rank::Two, Suit::Spades));
+
+        // under High, Q-K-A-Wild can only be J-Q-K-A
+        let config = wildcard_config(AceBehavior::High);
+        let run_cards = cards(&config, &[
+            (Rank::Queen, Suit::Hearts), (Rank::King, Suit::Hearts), (Rank::Ace, Suit::Hearts), (Rank::Two, Suit::Spades)
+        ]);
+        let Ok(run) = Run::new(run_cards) else { panic!("run should be valid") };
+        assert_eq!(run.cards[0].data(), (Rank::Two, Suit::Spades));
+    }
+
+    #[test]
+    fn run_cannot_cover_more_than_every_rank() {
+        // every Heart (with the Two of Hearts as a wildcard filling its own gap), plus another wildcard
+        let config = wildcard_config(AceBehavior::Low);
+        let mut run_cards = hearts(&config, &[
+            Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven,
+            Rank::Eight, Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen, Rank::King
+        ]);
+        run_cards.extend(cards(&config, &[(Rank::Two, Suit::Spades)]));
+        assert!(Run::new(run_cards).is_err());
+
+        let config = wildcard_config(AceBehavior::High);
+        let mut run_cards = hearts(&config, &[
+            Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven, Rank::Eight,
+            Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace
+        ]);
+        run_cards.extend(cards(&config, &[(Rank::Two, Suit::Spades)]));
+        assert!(Run::new(run_cards).is_err());
+    }
+
+    /// A config using Jokers as wildcards, with at most `max` per meld.
+    fn joker_config(max: usize) -> DeckConfig {
+        DeckConfig { use_joker: true, max_wildcards_per_meld: Some(max), ..DeckConfig::default() }
+    }
+
+    #[test]
+    fn wildcard_rank_limit() {
+        let config = DeckConfig { max_wildcards_per_meld: Some(1), ..wildcard_config(AceBehavior::Low) };
+        assert!(Set::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Five, Suit::Spades), (Rank::Two, Suit::Clubs)])).is_ok());
+        assert!(Set::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Two, Suit::Spades), (Rank::Two, Suit::Clubs)])).is_err());
+        assert!(Run::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Six, Suit::Hearts), (Rank::Two, Suit::Clubs)])).is_ok());
+        assert!(Run::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Two, Suit::Spades), (Rank::Two, Suit::Clubs)])).is_err());
+    }
+
+    #[test]
+    fn joker_limit() {
+        let config = joker_config(1);
+        assert!(Set::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Five, Suit::Spades), (Rank::Joker, Suit::Joker)])).is_ok());
+        assert!(Set::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Joker, Suit::Joker), (Rank::Joker, Suit::Joker)])).is_err());
+        assert!(Run::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Six, Suit::Hearts), (Rank::Joker, Suit::Joker)])).is_ok());
+        assert!(Run::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Joker, Suit::Joker), (Rank::Joker, Suit::Joker)])).is_err());
+    }
+
+    #[test]
+    fn all_wildcard_meld_is_rejected() {
+        let config = DeckConfig { use_joker: true, ..DeckConfig::default() };
+        let jokers = [(Rank::Joker, Suit::Joker); 3];
+        assert!(Set::new(cards(&config, &jokers)).is_err());
+        assert!(Run::new(cards(&config, &jokers)).is_err());
+    }
+
+    #[test]
+    fn wildcard_end_policy_allow() {
+        let config = wildcard_config(AceBehavior::Low);
+        // internal
+        assert!(Run::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Two, Suit::Spades), (Rank::Seven, Suit::Hearts)])).is_ok());
+        // high end
+        assert!(Run::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Six, Suit::Hearts), (Rank::Two, Suit::Spades)])).is_ok());
+        // low end, as King is the highest rank
+        assert!(Run::new(cards(&config, &[(Rank::Queen, Suit::Hearts), (Rank::King, Suit::Hearts), (Rank::Two, Suit::Spades)])).is_ok());
+    }
+
+    #[test]
+    fn wildcard_end_policy_deny() {
+        let config = DeckConfig { wildcard_end_policy: WildcardEndPolicy::Deny, ..wildcard_config(AceBehavior::Low) };
+        // internal
+        assert!(Run::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Two, Suit::Spades), (Rank::Seven, Suit::Hearts)])).is_ok());
+        // high end
+        assert!(Run::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Six, Suit::Hearts), (Rank::Two, Suit::Spades)])).is_err());
+        // low end
+        assert!(Run::new(cards(&config, &[(Rank::Queen, Suit::Hearts), (Rank::King, Suit::Hearts), (Rank::Two, Suit::Spades)])).is_err());
+
+        // laying a wildcard off to the end is also denied
+        let Ok(mut run) = Run::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Six, Suit::Hearts), (Rank::Seven, Suit::Hearts)])) else {
+            panic!("run should be valid")
+        };
+        let mut wildcard = cards(&config, &[(Rank::Two, Suit::Spades)]);
+        assert!(run.try_add_card(wildcard.remove(0)).is_err());
+    }
+
+    #[test]
+    fn min_meld_size() {
+        let config = DeckConfig { min_meld_size: 4, ..DeckConfig::default() };
+        assert!(Run::new(hearts(&config, &[Rank::Five, Rank::Six, Rank::Seven])).is_err());
+        assert!(Run::new(hearts(&config, &[Rank::Five, Rank::Six, Rank::Seven, Rank::Eight])).is_ok());
+        assert!(Set::new(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Five, Suit::Spades), (Rank::Five, Suit::Clubs)])).is_err());
+        assert!(Set::new(cards(&config, &[
+            (Rank::Five, Suit::Hearts), (Rank::Five, Suit::Spades), (Rank::Five, Suit::Clubs), (Rank::Five, Suit::Diamonds)
+        ])).is_ok());
+    }
+
+    /// Attempts to layoff a card with the given rank and suit to `meld`, returning the error if it fails.
+    fn try_layoff(meld: &mut Meld, config: &DeckConfig, card_data: (Rank, Suit)) -> Option<GameError> {
+        let card = cards(config, &[card_data]).remove(0);
+        meld.layoff_card(card, 0).err().map(|(_, err)| err)
+    }
+
+    #[test]
+    fn set_layoff_errors() {
+        let config = joker_config(1);
+        let Ok(mut set) = Meld::new_set(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Five, Suit::Spades), (Rank::Joker, Suit::Joker)])) else {
+            panic!("set should be valid")
+        };
+
+        assert_eq!(
+            try_layoff(&mut set, &config, (Rank::Six, Suit::Hearts)),
+            Some(GameError::WrongRank { expected: Rank::Five, actual: Rank::Six })
+        );
+        assert_eq!(try_layoff(&mut set, &config, (Rank::Joker, Suit::Joker)), Some(GameError::TooManyWildcards));
+        assert_eq!(try_layoff(&mut set, &config, (Rank::Five, Suit::Clubs)), None);
+        assert_eq!(set.layoffs(), &vec![((Rank::Five, Suit::Clubs), 0)]);
+    }
+
+    #[test]
+    fn run_layoff_errors() {
+        let config = joker_config(1);
+        let Ok(mut run) = Meld::new_run(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Six, Suit::Hearts), (Rank::Joker, Suit::Joker)])) else {
+            panic!("run should be valid")
+        };
+
+        assert_eq!(
+            try_layoff(&mut run, &config, (Rank::Eight, Suit::Spades)),
+            Some(GameError::WrongSuit { expected: Suit::Hearts, actual: Suit::Spades })
+        );
+        assert_eq!(try_layoff(&mut run, &config, (Rank::Nine, Suit::Hearts)), Some(GameError::NotAdjacent));
+        assert_eq!(try_layoff(&mut run, &config, (Rank::Joker, Suit::Joker)), Some(GameError::TooManyWildcards));
+        assert_eq!(try_layoff(&mut run, &config, (Rank::Eight, Suit::Hearts)), None);
+        assert_eq!(run.layoffs(), &vec![((Rank::Eight, Suit::Hearts), 0)]);
+    }
+
+    #[test]
+    fn full_run_layoff_error() {
+        let config = DeckConfig::default();
+        let Ok(mut run) = Meld::new_run(hearts(&config, &[
+            Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven,
+            Rank::Eight, Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen, Rank::King
+        ])) else {
+            panic!("run should be valid")
+        };
+
+        assert_eq!(try_layoff(&mut run, &config, (Rank::Five, Suit::Hearts)), Some(GameError::MeldFull));
+    }
+
+    #[test]
+    fn meld_invalid_after_wildcard_rank_removed() {
+        let config = wildcard_config(AceBehavior::Low);
+        let Ok(mut set) = Meld::new_set(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Five, Suit::Spades), (Rank::Two, Suit::Clubs)])) else {
+            panic!("set should be valid")
+        };
+        assert!(set.is_still_valid());
+
+        set.set_deck(Rc::new(Deck::new(DeckConfig::default()).unwrap()));
+        assert!(!set.is_still_valid());
+    }
+
+    /// Gets the rank and suit of each card in `meld`, in order.
+    fn meld_data(meld: &Meld) -> Vec<(Rank, Suit)> {
+        let cards = match meld {
+            Meld::Set(set) => &set.cards,
+            Meld::Run(run) => &run.cards
+        };
+        cards.iter().map(|card| card.data()).collect()
+    }
+
+    /// Serializes `meld` to JSON and back, tying it to a new deck made with `config`.
+    fn json_round_trip(meld: &Meld, config: &DeckConfig) -> Meld {
+        let json = serde_json::to_string(meld).unwrap();
+        let mut meld: Meld = serde_json::from_str(&json).unwrap();
+        meld.set_deck(Rc::new(Deck::new(config.clone()).unwrap()));
+        meld
+    }
+
+    #[test]
+    fn set_json_round_trip() {
+        let config = wildcard_config(AceBehavior::Low);
+        let Ok(mut set) = Meld::new_set(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Five, Suit::Spades), (Rank::Two, Suit::Clubs)])) else {
+            panic!("set should be valid")
+        };
+        assert!(set.layoff_card(cards(&config, &[(Rank::Five, Suit::Diamonds)]).remove(0), 1).is_ok());
+
+        let deserialized = json_round_trip(&set, &config);
+        assert_eq!(meld_data(&deserialized), meld_data(&set));
+        assert_eq!(deserialized.layoffs(), set.layoffs());
+        assert!(deserialized.is_still_valid());
+        let Meld::Set(deserialized_set) = deserialized else { panic!("meld should be a set") };
+        assert_eq!(deserialized_set.set_rank, Rank::Five);
+    }
+
+    #[test]
+    fn run_json_round_trip() {
+        let config = wildcard_config(AceBehavior::Low);
+        let Ok(mut run) = Meld::new_run(cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Six, Suit::Hearts), (Rank::Two, Suit::Clubs)])) else {
+            panic!("run should be valid")
+        };
+        assert!(run.layoff_card(cards(&config, &[(Rank::Eight, Suit::Hearts)]).remove(0), 2).is_ok());
+
+        let deserialized = json_round_trip(&run, &config);
+        assert_eq!(meld_data(&deserialized), meld_data(&run));
+        assert_eq!(deserialized.layoffs(), &vec![((Rank::Eight, Suit::Hearts), 2)]);
+        assert!(deserialized.is_still_valid());
+    }
+
+    #[test]
+    fn can_layoff_leaves_meld_unchanged() {
+        let config = DeckConfig::default();
+        let Ok(set) = Meld::new_set(cards(&config, &[(Rank::Five, Suit::Spades), (Rank::Five, Suit::Clubs), (Rank::Five, Suit::Diamonds)])) else {
+            panic!("set should be valid")
+        };
+        let Ok(run) = Meld::new_run(hearts(&config, &[Rank::Six, Rank::Seven, Rank::Eight])) else {
+            panic!("run should be valid")
+        };
+        let (set_data, run_data) = (meld_data(&set), meld_data(&run));
+
+        // the Five of Hearts fits both, the Nine of Hearts only fits the run, and the Nine of Spades fits neither
+        let cards = cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Nine, Suit::Hearts), (Rank::Nine, Suit::Spades)]);
+        assert!(set.can_layoff(&cards[0]));
+        assert!(run.can_layoff(&cards[0]));
+        assert!(!set.can_layoff(&cards[1]));
+        assert!(run.can_layoff(&cards[1]));
+        assert!(!set.can_layoff(&cards[2]));
+        assert!(!run.can_layoff(&cards[2]));
+
+        assert_eq!(meld_data(&set), set_data);
+        assert_eq!(meld_data(&run), run_data);
+        assert!(set.layoffs().is_empty() && run.layoffs().is_empty());
+    }
+
+    #[test]
+    fn new_set_and_run_only_form_their_kind() {
+        let config = DeckConfig::default();
+        let same_rank = [(Rank::Five, Suit::Hearts), (Rank::Five, Suit::Spades), (Rank::Five, Suit::Clubs)];
+        assert!(Meld::new_run(cards(&config, &same_rank)).is_err());
+        assert!(matches!(Meld::new(cards(&config, &same_rank)), Ok(Meld::Set(_))));
+
+        let same_suit = [Rank::Five, Rank::Six, Rank::Seven];
+        assert!(Meld::new_set(hearts(&config, &same_suit)).is_err());
+        assert!(matches!(Meld::new(hearts(&config, &same_suit)), Ok(Meld::Run(_))));
+    }
+
+    #[test]
+    fn new_prefers_set() {
+        // a Five with 2 wildcards could be a set of Fives or a run
+        let config = wildcard_config(AceBehavior::Low);
+        let either = cards(&config, &[(Rank::Five, Suit::Hearts), (Rank::Two, Suit::Clubs), (Rank::Two, Suit::Diamonds)]);
+        assert!(matches!(Meld::new(either), Ok(Meld::Set(_))));
+    }
+}
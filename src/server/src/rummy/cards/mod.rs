@@ -1,4 +1,5 @@
 pub mod suit_rank;
 pub mod card;
 pub mod meld;
-pub mod deck;
\ No newline at end of file
+pub mod deck;
+pub mod analysis;
\ No newline at end of file
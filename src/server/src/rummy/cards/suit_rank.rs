@@ -1,8 +1,9 @@
+use std::str::FromStr;
 use serde::{Serialize, Deserialize};
-use strum::EnumIter;
+use strum::{EnumIter, IntoEnumIterator};
 
 /// Poker suits.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, EnumIter)]
 pub enum Suit {
     Joker,
     Clubs,
@@ -12,7 +13,7 @@ pub enum Suit {
 }
 
 /// Poker ranks.    
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, EnumIter)]
 pub enum Rank {
     Joker,
     Ace,
@@ -29,3 +30,125 @@ pub enum Rank {
     Queen,
     King,
 }
+
+/// Parses a suit from its name or initial (case-insensitive), ie `"S"`/`"Spades"`.
+impl FromStr for Suit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "JOKER" => Ok(Suit::Joker),
+            "C" | "CLUBS" => Ok(Suit::Clubs),
+            "D" | "DIAMONDS" => Ok(Suit::Diamonds),
+            "H" | "HEARTS" => Ok(Suit::Hearts),
+            "S" | "SPADES" => Ok(Suit::Spades),
+            _ => Err(format!("Invalid suit '{s}' (expected one of C, D, H, S, or Joker)"))
+        }
+    }
+}
+
+/// Parses a rank from its number, initial or name (case-insensitive), ie `"10"`/`"T"`/`"Ten"`.
+impl FromStr for Rank {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "JOKER" => Ok(Rank::Joker),
+            "A" | "1" | "ACE" => Ok(Rank::Ace),
+            "2" | "TWO" => Ok(Rank::Two),
+            "3" | "THREE" => Ok(Rank::Three),
+            "4" | "FOUR" => Ok(Rank::Four),
+            "5" | "FIVE" => Ok(Rank::Five),
+            "6" | "SIX" => Ok(Rank::Six),
+            "7" | "SEVEN" => Ok(Rank::Seven),
+            "8" | "EIGHT" => Ok(Rank::Eight),
+            "9" | "NINE" => Ok(Rank::Nine),
+            "10" | "T" | "TEN" => Ok(Rank::Ten),
+            "J" | "JACK" => Ok(Rank::Jack),
+            "Q" | "QUEEN" => Ok(Rank::Queen),
+            "K" | "KING" => Ok(Rank::King),
+            _ => Err(format!("Invalid rank '{s}' (expected one of A, 2-10, T, J, Q, K, or Joker)"))
+        }
+    }
+}
+
+/// Converts a suit's discriminant (ie `suit as u8`) back into the suit.
+impl TryFrom<u8> for Suit {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Suit::iter()
+            .find(|&suit| suit as u8 == value)
+            .ok_or(format!("Invalid suit value ({value})"))
+    }
+}
+
+/// Converts a rank's discriminant (ie `rank as u8`) back into the rank.
+impl TryFrom<u8> for Rank {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Rank::iter()
+            .find(|&rank| rank as u8 == value)
+            .ok_or(format!("Invalid rank value ({value})"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid_suits() {
+        assert_eq!("S".parse(), Ok(Suit::Spades));
+        assert_eq!("h".parse(), Ok(Suit::Hearts));
+        assert_eq!("Diamonds".parse(), Ok(Suit::Diamonds));
+        assert_eq!("clubs".parse(), Ok(Suit::Clubs));
+        assert_eq!("JOKER".parse(), Ok(Suit::Joker));
+    }
+
+    #[test]
+    fn parse_invalid_suits() {
+        assert!("".parse::<Suit>().is_err());
+        assert!("X".parse::<Suit>().is_err());
+        assert!("Spade".parse::<Suit>().is_err());
+    }
+
+    #[test]
+    fn parse_valid_ranks() {
+        assert_eq!("A".parse(), Ok(Rank::Ace));
+        assert_eq!("1".parse(), Ok(Rank::Ace));
+        assert_eq!("7".parse(), Ok(Rank::Seven));
+        assert_eq!("10".parse(), Ok(Rank::Ten));
+        assert_eq!("t".parse(), Ok(Rank::Ten));
+        assert_eq!("Ten".parse(), Ok(Rank::Ten));
+        assert_eq!("q".parse(), Ok(Rank::Queen));
+        assert_eq!("joker".parse(), Ok(Rank::Joker));
+    }
+
+    #[test]
+    fn parse_invalid_ranks() {
+        assert!("".parse::<Rank>().is_err());
+        assert!("0".parse::<Rank>().is_err());
+        assert!("11".parse::<Rank>().is_err());
+        assert!("X".parse::<Rank>().is_err());
+    }
+
+    #[test]
+    fn suit_u8_round_trip() {
+        for suit in Suit::iter() {
+            assert_eq!(Suit::try_from(suit as u8), Ok(suit));
+        }
+        assert!(Suit::try_from(5).is_err());
+        assert!(Suit::try_from(u8::MAX).is_err());
+    }
+
+    #[test]
+    fn rank_u8_round_trip() {
+        for rank in Rank::iter() {
+            assert_eq!(Rank::try_from(rank as u8), Ok(rank));
+        }
+        assert!(Rank::try_from(14).is_err());
+        assert!(Rank::try_from(u8::MAX).is_err());
+    }
+}
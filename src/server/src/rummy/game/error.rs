@@ -1 +1,77 @@
-// TODO!
\ No newline at end of file
+use crate::rummy::cards::suit_rank::{Rank, Suit};
+
+/// A common error type for the Rummy game.
+///
+/// As this implements `std::error::Error` (via `thiserror`), it can be propagated with `?`
+/// into `anyhow::Error`, or into `HttpError` where it's mapped to an appropriate status code.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    /// An action was attempted outside of the phase it's allowed in.
+    #[error("action isn't allowed in the current game phase")]
+    WrongPhase,
+
+    /// The game or deck was configured with an invalid combination of settings.
+    #[error("invalid config: {0}")]
+    InvalidConfig(String),
+
+    /// A draw was attempted for more cards than are available.
+    #[error("draw amount ({requested}) greater than available cards ({available})")]
+    NotEnoughCards { requested: usize, available: usize },
+
+    /// A draw was attempted from an empty discard pile.
+    #[error("can't draw from empty discard pile")]
+    EmptyDiscardPile,
+
+    /// A draw was attempted for 0 cards.
+    #[error("must draw at least one card")]
+    ZeroDrawAmount,
+
+    /// An index into a hand/pile/etc was out of bounds.
+    #[error("index ({index}) out of bounds (length {len})")]
+    InvalidIndex { index: usize, len: usize },
+
+    /// The same index was given more than once (ie referring to the same card twice).
+    #[error("index ({0}) given more than once")]
+    DuplicateIndex(usize),
+
+    /// A card was laid off to a set of a different rank.
+    #[error("card's rank ({actual:?}) doesn't match the set's rank ({expected:?})")]
+    WrongRank { expected: Rank, actual: Rank },
+
+    /// A card was laid off to a run of a different suit.
+    #[error("card's suit ({actual:?}) doesn't match the run's suit ({expected:?})")]
+    WrongSuit { expected: Suit, actual: Suit },
+
+    /// A card was laid off to a run, but doesn't fit at either end (or in a gap).
+    #[error("card doesn't extend the run")]
+    NotAdjacent,
+
+    /// A wildcard was laid off to a meld that already has the deck's maximum number of wildcards.
+    #[error("meld already has the maximum number of wildcards")]
+    TooManyWildcards,
+
+    /// A card was laid off to a run that already covers every rank.
+    #[error("run is already full")]
+    MeldFull,
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrong_phase() -> Result<(), GameError> {
+        Err(GameError::WrongPhase)
+    }
+
+    fn propagate() -> anyhow::Result<()> {
+        wrong_phase()?;
+        Ok(())
+    }
+
+    #[test]
+    fn converts_into_anyhow() {
+        let err = propagate().unwrap_err();
+        assert_eq!(err.downcast_ref::<GameError>(), Some(&GameError::WrongPhase));
+    }
+}
@@ -1,4 +1,6 @@
 use axum::middleware::Next;
+use super::error::GameError;
+use crate::rummy::cards::suit_rank::{Rank, Suit};
 
 /// Trait indicating a game phase.
 trait GamePhase {}
@@ -17,7 +19,9 @@ struct DiscardPhase {
     pub(super) has_discarded: usize
 }
 struct RoundEndPhase {
-    pub(super) has_scored_round: bool
+    pub(super) has_scored_round: bool,
+    pub(super) reason: RoundEndReason
+    // TODO: once there's a per-player view of the game, entering this phase should reveal every hand to all players
 }
 struct GameEndPhase {
     // no state needed, game has ended
@@ -44,6 +48,36 @@ pub enum NextPhase<P: GamePhase> {
     End(G<RoundEndPhase>)
 }
 
+/// Why a round ended:
+/// - PlayerWentOut: A player emptied their hand.
+/// - StockDepleted: The stock ran out and couldn't be replenished.
+/// - OnePlayerLeft: All but one player quit.
+/// - Stalemate: No player can draw or go out.
+/// 
+/// If several of these happen at once, the first one listed here is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundEndReason {
+    PlayerWentOut { player_id: usize },
+    StockDepleted,
+    OnePlayerLeft,
+    Stalemate
+}
+
+/// A single action a player can take on their turn:
+/// - DrawStock: Draw a card from the stock.
+/// - DrawDiscard: Draw `amount` cards from the discard pile (`None` for the whole pile).
+/// - FormMeld: Form a meld from the cards at `card_indices` in the player's hand.
+/// - Layoff: Layoff the card at `card_i` to a target player's meld.
+/// - Discard: Discard the card at `card_i`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    DrawStock,
+    DrawDiscard { amount: Option<usize> },
+    FormMeld { card_indices: Vec<usize> },
+    Layoff { card_i: usize, target_player_i: usize, target_meld_i: usize },
+    Discard { card_i: usize }
+}
+
 /// Trait for transitioning from one phase to another.
 /// 
 /// As it is infallible, there should be some default behaviour if the game 
@@ -57,25 +91,65 @@ pub trait PhaseTransition<P: GamePhase> {
 
 /// Trait for actions during DrawPhase.
 pub trait DrawActions {
-    fn draw_stock(&mut self) -> Result<(), String>;
-    fn draw_discard_pile(&mut self) -> Result<(), String>;
+    fn draw_stock(&mut self) -> Result<(), GameError>;
+    fn draw_discard_pile(&mut self) -> Result<(), GameError>;
+
+    /// Returns whether drawing `amount` cards from the discard pile (`None` for the whole pile)
+    /// would let the current player form a new meld, or layoff to an existing one.
+    /// 
+    /// This doesn't draw anything.
+    fn discard_draw_completes_meld(&self, amount: Option<usize>) -> bool;
 }
 
 /// Trait for actions during PlayPhase.
 pub trait PlayActions {
     /// Form a meld from a Vec of indices,
     /// referring to cards in the current player's hand.
-    fn form_meld(&mut self, card_indices: Vec<usize>) -> Result<(), String>;
+    /// 
+    /// If this empties the player's hand, they go out and the round ends.
+    fn form_meld(&mut self, card_indices: Vec<usize>) -> Result<(), GameError>;
+
+    /// Form a meld from the given cards' ranks and suits,
+    /// which are looked up in the current player's hand (so callers don't need to track indices).
+    /// 
+    /// If any card isn't in the player's hand, an `Err` is returned.
+    fn form_meld_from_cards(&mut self, cards: Vec<(Rank, Suit)>) -> Result<(), GameError>;
+
+    /// Returns whether forming a meld from these indices would empty the current player's hand
+    /// (ie they would go out and end the round).
+    /// 
+    /// This doesn't check whether the meld is actually valid.
+    fn meld_would_go_out(&self, card_indices: &[usize]) -> bool;
 
     /// Layoff a chosen card in the current player's hand,
     /// to a chosen player's chosen meld.
-    fn layoff_card(&mut self, card_i: usize, target_player_i: usize, target_meld_i: usize) -> Result<(), String>;
+    fn layoff_card(&mut self, card_i: usize, target_player_i: usize, target_meld_i: usize) -> Result<(), GameError>;
+
+    /// Layoff multiple cards in the current player's hand, in the given order,
+    /// to a chosen player's chosen meld.
+    /// 
+    /// `card_indices` refer to the hand before any cards are removed.
+    /// If any card can't be laid off, none of them are (ie the hand and meld are left unchanged).
+    fn layoff_cards(&mut self, card_indices: Vec<usize>, target_player_i: usize, target_meld_i: usize) -> Result<(), GameError>;
+
+    /// Returns every `(target_player_i, target_meld_i)` that the card at `card_i`
+    /// in the current player's hand could be laid off to.
+    fn layoff_targets(&self, card_i: usize) -> Vec<(usize, usize)>;
+
+    /// Returns whether the current player can form any meld or layoff any card.
+    /// 
+    /// If not, the only thing left to do is transition to DiscardPhase.
+    fn has_any_play(&self) -> bool where Self: PlayableActions {
+        self.legal_actions()
+            .iter()
+            .any(|action| matches!(action, Action::FormMeld { .. } | Action::Layoff { .. }))
+    }
 }
 
 /// Trait for actions during DiscardPhase.
 pub trait DiscardActions {
     /// Discard a card for current player at given index in their hand.
-    fn discard(&mut self, card_i: usize) -> Result<(), String>;
+    fn discard(&mut self, card_i: usize) -> Result<(), GameError>;
 }
 
 /// Trait for actions during RoundEndPhase.
@@ -83,7 +157,7 @@ pub trait RoundEndActions {
     type EndedGame;
 
     /// Calculate the round's score.
-    fn calculate_score(&mut self) -> Result<(), String>;
+    fn calculate_score(&mut self) -> Result<(), GameError>;
 
     /// End the game.
     fn end_game(self) -> Self::EndedGame;
@@ -100,6 +174,27 @@ pub trait PlayableActions {
 
     /// Sets a player as having quit.
     fn quit_player(&mut self, player_i: usize);
+
+    /// Sets a player who has quit as active again.
+    /// 
+    /// Like a newly added player, they only resume from the next round,
+    /// so any cards they held when quitting aren't returned to them.
+    /// 
+    /// Returns `Err` if there is no such player, or they're already active.
+    fn reactivate_player(&mut self, player_id: usize) -> Result<(), GameError>;
+
+    /// Returns every action the current player can legally take in this phase.
+    /// 
+    /// This shouldn't mutate the game; it only checks each possible action
+    /// (ie every meldable combination of cards, every valid layoff target).
+    fn legal_actions(&self) -> Vec<Action>;
+
+    /// Returns every action the player with `player_id` can legally take,
+    /// which is empty unless it's currently their turn.
+    /// 
+    /// Unlike `legal_actions`, this is safe to send to that player's client,
+    /// as it only uses information visible to them.
+    fn legal_actions_for(&self, player_id: usize) -> Vec<Action>;
 }
 
 pub struct Game<P: GamePhase> {
@@ -1,10 +1,13 @@
+use serde::{Serialize, Deserialize};
 use crate::rummy::player::{self, Player};
 use crate::rummy::game::state::{GamePhase, GameState};
+use crate::rummy::game::error::GameError;
 use crate::rummy::cards::{
     meld::{Meld, Set, Run}
     card::Card,
     deck::{Deck, DeckConfig}
 };
+use super::RummyVariant;
 use super::super::traits::{
     GameInit,
     GameActions,
@@ -13,6 +16,11 @@ use super::super::traits::{
 
 
 /// Holds customizable settings for a basic Rummy game.
+/// 
+/// **Note**: Like `DeckConfig`, this is `non_exhaustive`; outside this crate,
+/// start from `BasicConfig::default()` and set the fields you need.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct BasicConfig {
     pub deck_config: DeckConfig
 }
@@ -28,28 +36,6 @@ pub struct BasicRummy {
 }
 
 impl BasicRummy {
-    /// Get the amount of cards to deal given the player and pack count;
-    /// follows the [Wiki rules](https://en.wikipedia.org/wiki/Rummy#Basic_rummy).
-    /// 
-    /// If the counts don't follow the rules, an `Err` is returned.
-    fn get_deal_count(player_count: usize, pack_count: usize) -> Result<usize, String> {
-        let deal_count = match (player_count, pack_count) {
-            (2, 1) => 10,
-            (3..=10, 1) => 7,
-            (3..=10, _) => 10,
-            (6, 1) => 6,
-            (6, _) => 10,
-            (7, 2) => 10,
-            _ => {
-                return Err(format!(
-                    "Unallowed player count ({player_count}) and pack count ({pack_count})"
-                ));
-            }
-        };
-    
-        Ok(deal_count)
-    }
-
     /// Gets the number of currently active players.
     fn get_active_players(&self) -> usize {
         self.players
@@ -69,6 +55,24 @@ impl BasicRummy {
     }
 }
 
+impl RummyVariant for BasicRummy {
+    /// Follows the [Wiki rules](https://en.wikipedia.org/wiki/Rummy#Basic_rummy).
+    fn cards_to_deal(num_players: usize, num_decks: usize) -> Result<usize, GameError> {
+        let deal_count = match (num_players, num_decks) {
+            (2, 1) => 10,
+            (3..=5, 1) => 7,
+            (6, 1) => 6,
+            (3..=7, 2) => 10,
+            _ => {
+                return Err(GameError::InvalidConfig(format!(
+                    "Unallowed player count ({num_players}) and pack count ({num_decks})"
+                )));
+            }
+        };
+    
+        Ok(deal_count)
+    }
+}
 
 impl GameInit for BasicRummy {
     type Config = BasicConfig;
@@ -76,18 +80,21 @@ impl GameInit for BasicRummy {
     /// Create a basic Rummy game. Note the following constraints:
     /// - 2-7 players only
     /// - 3-6 players may choose between using 1 or 2 decks
+    /// - 2 players must use 1 deck, and 7 players must use 2 decks
     /// 
     /// Breaking a constraint in `config` will return an `Err`.
     fn new(player_ids: Vec<usize>, config: Self::Config) -> Result<Self, String> {
         let pack_count = config.deck_config.pack_count;
         let player_count = player_ids.len();
-        BasicRummy::get_deal_count(player_count, pack_count)?; // TODO: is it a good idea to use this for validation too?
+        BasicRummy::cards_to_deal(player_count, pack_count)
+            .map_err(|err| err.to_string())?; // TODO: is it a good idea to use this for validation too?
 
         let state = GameState::new();
-        let deck = Deck::new(config.deck_config)?;
+        let deck = Deck::new(config.deck_config)
+            .map_err(|err| err.to_string())?;
         let players = player_ids
             .iter()
-            .map(|&id| Player::new(id))
+            .map(|&id| Player::new(id, 0, true))
             .collect();
 
         Ok(
@@ -104,10 +111,22 @@ impl GameInit for BasicRummy {
 
         let pack_count = self.config.deck_config.pack_count;
         let player_count = self.get_active_players();
-        let deal_count = BasicRummy::get_deal_count(player_count, pack_count)?;
+        let deal_count = BasicRummy::cards_to_deal(player_count, pack_count)
+            .map_err(|err| err.to_string())?;
+
+        // check up front, so we never deal to only some players
+        let total_deal_count = deal_count * player_count;
+        if total_deal_count > self.deck.get_cards().len() {
+            return Err(format!(
+                "Not enough cards in stock ({}) to deal {deal_count} to each active player ({total_deal_count} total)",
+                self.deck.get_cards().len()
+            ));
+        }
 
-        for player in &self.players {
-            let mut cards = self.deck.draw(deal_count).unwrap();
+        // only active players are dealt in, matching `player_count` above
+        for player in self.players.iter_mut().filter(|player| player.active) {
+            let mut cards = self.deck.draw(deal_count)
+                .map_err(|err| err.to_string())?;
             player.cards.append(&mut cards);
         }
 
@@ -136,6 +155,9 @@ impl GameActions for BasicRummy {
 
     fn form_meld(&mut self, indices: Vec<usize>) -> Result<(), String> {
         self.verify_gamephase(GamePhase::PlayerPlays)?;
+        self.get_current_player()
+            .validate_card_indices(&indices)
+            .map_err(|err| err.to_string())?;
 
         let cards = self.get_current_player().cards
             .iter()
@@ -173,3 +195,42 @@ impl GameAdmin for BasicRummy {
         self.verify_gamephase(GamePhase::GameEnd)?;
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rummy::cards::{deck::AceBehavior, suit_rank::Rank};
+
+    #[test]
+    fn cards_to_deal_follows_table() {
+        assert_eq!(BasicRummy::cards_to_deal(2, 1), Ok(10));
+        for num_players in 3..=5 {
+            assert_eq!(BasicRummy::cards_to_deal(num_players, 1), Ok(7));
+        }
+        assert_eq!(BasicRummy::cards_to_deal(6, 1), Ok(6));
+        for num_players in 3..=7 {
+            assert_eq!(BasicRummy::cards_to_deal(num_players, 2), Ok(10));
+        }
+    }
+
+    #[test]
+    fn cards_to_deal_rejects_unallowed_counts() {
+        for (num_players, num_decks) in [(0, 1), (1, 1), (2, 2), (7, 1), (8, 1), (8, 2), (10, 2), (4, 0), (4, 3)] {
+            assert!(BasicRummy::cards_to_deal(num_players, num_decks).is_err());
+        }
+    }
+
+    #[test]
+    fn config_json_round_trip() {
+        let deck_config = DeckConfig {
+            ace_behavior: AceBehavior::High,
+            wildcard_rank: Some(Rank::Two),
+            min_meld_size: 4,
+            ..DeckConfig::default()
+        };
+        let config = BasicConfig { deck_config };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<BasicConfig>(&json).unwrap(), config);
+    }
+}
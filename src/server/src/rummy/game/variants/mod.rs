@@ -1 +1,11 @@
-pub mod basic;
\ No newline at end of file
+pub mod basic;
+
+use super::error::GameError;
+
+/// Trait for rules that differ between Rummy variants.
+pub trait RummyVariant {
+    /// Get the amount of cards to deal to each player, given the player and pack count.
+    /// 
+    /// If the variant doesn't allow these counts, an `Err` is returned.
+    fn cards_to_deal(num_players: usize, num_decks: usize) -> Result<usize, GameError>;
+}
\ No newline at end of file
@@ -1,21 +1,37 @@
+use serde::{Serialize, Deserialize};
 use super::cards::{card::Card, meld::Meld};
+use super::game::error::GameError;
+
+/// How to sort a player's hand:
+/// - ByRank: By rank, then suit (ie the cards' normal ordering)
+/// - BySuit: By suit, then rank
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HandSort {
+    ByRank,
+    BySuit
+}
 
 /// A Rummy player.
-pub(crate) struct Player {
+pub struct Player {
     pub(crate) id: usize,
     pub(crate) cards: Vec<Card>,
     pub(crate) melds: Vec<Meld>,
-    pub(crate) active: bool
+    pub(crate) active: bool,
+    pub(crate) joined_in_round: usize
 }
 
 impl Player {
-    /// Creates a new player.
-    pub(crate) fn new(id: usize) -> Self {
+    /// Creates a new player, who joined in round `joined_in_round`.
+    /// 
+    /// If they're joining in the middle of a round, `active` should be false,
+    /// so they only start playing from the next round.
+    pub(crate) fn new(id: usize, joined_in_round: usize, active: bool) -> Self {
         Player {
             id,
             cards: Vec::new(),
             melds: Vec::new(),
-            active: true
+            active,
+            joined_in_round
         }
     }
 
@@ -27,4 +43,103 @@ impl Player {
         self.cards.clear();
         self.melds.clear();
     }
-}
\ No newline at end of file
+
+    /// Sorts the player's hand by `sort`.
+    pub(crate) fn sort_hand(&mut self, sort: HandSort) {
+        match sort {
+            HandSort::ByRank => self.cards.sort(),
+            HandSort::BySuit => self.cards.sort_by(|a, b| a.suit.cmp(&b.suit).then(a.cmp(b)))
+        }
+    }
+
+    /// Checks that every index refers to a card in the player's hand,
+    /// and that no index is given more than once.
+    pub(crate) fn validate_card_indices(&self, card_indices: &[usize]) -> Result<(), GameError> {
+        for (i, &index) in card_indices.iter().enumerate() {
+            if index >= self.cards.len() {
+                return Err(GameError::InvalidIndex { index, len: self.cards.len() });
+            }
+            if card_indices[..i].contains(&index) {
+                return Err(GameError::DuplicateIndex(index));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read-only getters
+impl Player {
+    /// Get the player's ID.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Get whether the player is currently active (ie hasn't quit).
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Get the round the player joined in.
+    pub fn joined_round(&self) -> usize {
+        self.joined_in_round
+    }
+
+    /// Get the number of cards in the player's hand.
+    pub fn card_count(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Iterate over the player's hand, along with each card's index
+    /// (ie for choosing indices to meld/layoff/discard).
+    pub fn enumerate_hand(&self) -> impl Iterator<Item = (usize, &Card)> {
+        self.cards.iter().enumerate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use super::*;
+    use crate::rummy::cards::{deck::{Deck, DeckConfig}, suit_rank::{Rank, Suit}};
+
+    /// Creates a player holding cards with the given ranks and suits.
+    fn player_with_hand(hand: &[(Rank, Suit)]) -> Player {
+        let deck = Rc::new(Deck::new(DeckConfig::default()).unwrap());
+        let mut player = Player::new(0, 0, true);
+        player.cards = hand
+            .iter()
+            .map(|&(rank, suit)| Card { rank, suit, deck: deck.clone() })
+            .collect();
+        player
+    }
+
+    #[test]
+    fn validate_card_indices() {
+        let player = player_with_hand(&[(Rank::Two, Suit::Hearts), (Rank::Three, Suit::Hearts), (Rank::Four, Suit::Hearts)]);
+
+        assert_eq!(player.validate_card_indices(&[2, 0]), Ok(()));
+        assert_eq!(player.validate_card_indices(&[0, 0, 1]), Err(GameError::DuplicateIndex(0)));
+        assert_eq!(player.validate_card_indices(&[0, 3]), Err(GameError::InvalidIndex { index: 3, len: 3 }));
+    }
+
+    #[test]
+    fn sort_hand() {
+        let mut player = player_with_hand(&[
+            (Rank::King, Suit::Spades), (Rank::Two, Suit::Hearts), (Rank::Three, Suit::Clubs),
+            (Rank::Two, Suit::Clubs), (Rank::Ace, Suit::Diamonds)
+        ]);
+        let hand = |player: &Player| player.cards.iter().map(|card| card.data()).collect::<Vec<_>>();
+
+        player.sort_hand(HandSort::ByRank);
+        assert_eq!(hand(&player), vec![
+            (Rank::Ace, Suit::Diamonds), (Rank::Two, Suit::Clubs), (Rank::Two, Suit::Hearts),
+            (Rank::Three, Suit::Clubs), (Rank::King, Suit::Spades)
+        ]);
+
+        player.sort_hand(HandSort::BySuit);
+        assert_eq!(hand(&player), vec![
+            (Rank::Two, Suit::Clubs), (Rank::Three, Suit::Clubs), (Rank::Ace, Suit::Diamonds),
+            (Rank::Two, Suit::Hearts), (Rank::King, Suit::Spades)
+        ]);
+    }
+}